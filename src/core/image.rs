@@ -0,0 +1,102 @@
+use std::fmt::Debug;
+
+use crate::core::{ports::Ports, WaitFor};
+
+/// Represents a docker image.
+///
+/// Implementations are required to implement Default. The default instance of an [`Image`]
+/// should have a meaningful configuration! It should be possible to run the default
+/// instance of an Image and get back a working container!
+///
+/// [`Image`]: trait.Image.html
+pub trait Image
+where
+    Self: Sized,
+    Self::Args: IntoIterator<Item = String>,
+{
+    /// A type representing the environment-specific arguments for this image.
+    type Args;
+
+    /// The name of the docker image to pull from the Docker Hub registry.
+    fn name(&self) -> String;
+
+    /// Implementations are encouraged to include a tag that will not change (i.e. NOT latest)
+    /// in order to prevent test code from randomly breaking because the underlying docker
+    /// suddenly changed.
+    fn tag(&self) -> String;
+
+    /// Returns a list of conditions that need to be met before a started container is considered ready.
+    ///
+    /// This method is the **🍞 and butter** of the whole testcontainers library. Containers are
+    /// rarely instantly available as soon as they are started. Most of them take some time to boot
+    /// up.
+    fn ready_conditions(&self) -> Vec<WaitFor>;
+
+    /// Returns the environment variables that needs to be set when a container is created.
+    fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        Box::new(std::iter::empty())
+    }
+
+    /// Returns the commands that needs to be executed after a container is started i.e. commands
+    /// to be run in a running container.
+    ///
+    /// This is useful when certain re-configuration is required after the start
+    /// of container for the container to be considered ready for use in tests.
+    fn exec_after_start(&self, _cs: &ContainerState) -> Vec<ExecCommand> {
+        Vec::new()
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecCommand {
+    cmd: Vec<String>,
+    ready_condition: Option<WaitFor>,
+}
+
+impl ExecCommand {
+    /// Command to be executed, as a list of argv tokens.
+    pub fn new(cmd: Vec<String>) -> Self {
+        Self {
+            cmd,
+            ready_condition: None,
+        }
+    }
+
+    /// Condition that needs to be met before the next command is executed.
+    pub fn with_ready_condition(mut self, ready_condition: WaitFor) -> Self {
+        self.ready_condition = Some(ready_condition);
+        self
+    }
+
+    pub fn cmd(&self) -> &[String] {
+        &self.cmd
+    }
+
+    pub fn ready_condition(&self) -> Option<&WaitFor> {
+        self.ready_condition.as_ref()
+    }
+}
+
+/// Represents the current state of a running container, exposed to [`Image::exec_after_start`] so
+/// that follow-up commands can reference the host-mapped ports of the container.
+#[derive(Debug)]
+pub struct ContainerState {
+    ports: Ports,
+}
+
+impl ContainerState {
+    pub fn new(ports: Ports) -> Self {
+        Self { ports }
+    }
+
+    /// Returns the host port the given internal port was mapped to when the container started.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal port was not exposed by the container.
+    pub fn host_port(&self, internal_port: u16) -> u16 {
+        self.ports.map_to_host_port(internal_port).unwrap_or_else(|| {
+            panic!("port {internal_port} is not exposed, cannot resolve its host mapping")
+        })
+    }
+}
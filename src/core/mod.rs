@@ -0,0 +1,9 @@
+pub use self::{
+    image::{ContainerState, ExecCommand, Image},
+    ports::Ports,
+    wait_for::WaitFor,
+};
+
+mod image;
+mod ports;
+mod wait_for;
@@ -0,0 +1,20 @@
+use std::collections::HashMap;
+
+/// The exposed ports of a running container.
+#[derive(Clone, Debug, Default)]
+pub struct Ports {
+    mapping: HashMap<u16, u16>,
+}
+
+impl Ports {
+    /// Registers the mapping of an exposed container port to the port it is bound to on the host.
+    pub fn add_mapping(&mut self, internal: u16, host: u16) -> &mut Self {
+        self.mapping.insert(internal, host);
+        self
+    }
+
+    /// Returns the host port the given internal port is mapped to, if any.
+    pub fn map_to_host_port(&self, internal_port: u16) -> Option<u16> {
+        self.mapping.get(&internal_port).copied()
+    }
+}
@@ -0,0 +1,84 @@
+use std::{env::var, time::Duration};
+
+use regex::Regex;
+
+/// Represents a condition that needs to be met before a container is considered ready.
+#[derive(Debug, Clone)]
+pub enum WaitFor {
+    /// An empty condition. Useful for default cases or fallbacks.
+    Nothing,
+    /// Wait for a message on the stdout stream of the container's logs.
+    StdOutMessage { message: String },
+    /// Wait for a message on the stderr stream of the container's logs.
+    StdErrMessage { message: String },
+    /// Wait for a log line matching the given regular expression.
+    MessageMatching { pattern: Regex },
+    /// Wait until every nested condition has been satisfied at least once.
+    AllOf { conditions: Vec<WaitFor> },
+    /// Wait for a certain amount of time.
+    Duration { length: Duration },
+    /// Wait for the container's status to become `healthy`.
+    Healthcheck,
+}
+
+impl WaitFor {
+    /// Wait for the message to appear on the container's stdout.
+    pub fn message_on_stdout<S: Into<String>>(message: S) -> WaitFor {
+        WaitFor::StdOutMessage {
+            message: message.into(),
+        }
+    }
+
+    /// Wait for the message to appear on the container's stderr.
+    pub fn message_on_stderr<S: Into<String>>(message: S) -> WaitFor {
+        WaitFor::StdErrMessage {
+            message: message.into(),
+        }
+    }
+
+    /// Wait for a log line matching the given regular expression.
+    pub fn message_matching(pattern: Regex) -> WaitFor {
+        WaitFor::MessageMatching { pattern }
+    }
+
+    /// Wait until every one of the given conditions has been satisfied at least once.
+    pub fn all_of(conditions: Vec<WaitFor>) -> WaitFor {
+        WaitFor::AllOf { conditions }
+    }
+
+    /// Wait for the container to become healthy.
+    ///
+    /// If you use this wait condition, you have to make sure that the container has a
+    /// `HEALTHCHECK` instruction defined, otherwise the readiness check will hang forever.
+    pub fn healthcheck() -> WaitFor {
+        WaitFor::Healthcheck
+    }
+
+    /// Wait for a certain amount of seconds.
+    pub fn seconds(length: u64) -> WaitFor {
+        WaitFor::Duration {
+            length: Duration::from_secs(length),
+        }
+    }
+
+    /// Wait for a certain amount of millis.
+    pub fn millis(length: u64) -> WaitFor {
+        WaitFor::Duration {
+            length: Duration::from_millis(length),
+        }
+    }
+
+    /// Wait for a certain amount of millis specified in the given environment variable.
+    pub fn millis_in_env_var(name: &'static str) -> WaitFor {
+        let additional_sleep_period = var(name).map(|value| value.parse());
+
+        (|| {
+            let length = additional_sleep_period.ok()?.ok()?;
+
+            Some(WaitFor::Duration {
+                length: Duration::from_millis(length),
+            })
+        })()
+        .unwrap_or(WaitFor::Nothing)
+    }
+}
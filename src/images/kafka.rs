@@ -1,10 +1,14 @@
-use crate::{core::WaitFor, Image};
+use crate::{
+    core::{ContainerState, ExecCommand, WaitFor},
+    Image,
+};
 use std::collections::HashMap;
 
 const NAME: &str = "confluentinc/cp-kafka";
 const DEFAULT_TAG: &str = "6.1.1";
 
 pub const KAFKA_PORT: u16 = 9093;
+const BROKER_PORT: u16 = 9092;
 const ZOOKEEPER_PORT: u16 = 2181;
 
 #[derive(Clone, Debug, Default)]
@@ -34,11 +38,178 @@ zookeeper-server-start zookeeper.properties &
     }
 }
 
+/// The security protocol a [`Kafka`] broker advertises on its external listener.
+///
+/// Only plaintext transports are supported: `SASL_SSL` would require keystore/truststore material
+/// that this image has no way to generate and inject, so it is intentionally omitted rather than
+/// offered as a protocol that silently produces a broker which can't start its SSL listener.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SecurityProtocol {
+    Plaintext,
+    SaslPlaintext,
+}
+
+impl SecurityProtocol {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SecurityProtocol::Plaintext => "PLAINTEXT",
+            SecurityProtocol::SaslPlaintext => "SASL_PLAINTEXT",
+        }
+    }
+
+    fn is_sasl(&self) -> bool {
+        !matches!(self, SecurityProtocol::Plaintext)
+    }
+}
+
+/// The SASL mechanism the external listener authenticates clients with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SaslMechanism {
+    Plain,
+    ScramSha256,
+    ScramSha512,
+}
+
+impl SaslMechanism {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SaslMechanism::Plain => "PLAIN",
+            SaslMechanism::ScramSha256 => "SCRAM-SHA-256",
+            SaslMechanism::ScramSha512 => "SCRAM-SHA-512",
+        }
+    }
+
+    /// The JAAS login module class backing this mechanism.
+    fn login_module(&self) -> &'static str {
+        match self {
+            SaslMechanism::Plain => "org.apache.kafka.common.security.plain.PlainLoginModule",
+            SaslMechanism::ScramSha256 | SaslMechanism::ScramSha512 => {
+                "org.apache.kafka.common.security.scram.ScramLoginModule"
+            }
+        }
+    }
+
+    /// The mechanism name as it appears in a `KAFKA_LISTENER_NAME_*` env var key, where the
+    /// hyphens of the SCRAM mechanisms aren't valid.
+    fn env_token(&self) -> String {
+        self.as_str().replace('-', "_")
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Topic {
+    name: String,
+    partitions: u32,
+    replication: u32,
+}
+
 #[derive(Debug)]
 pub struct Kafka {
     arguments: KafkaArgs,
     env_vars: HashMap<String, String>,
     tag: String,
+    topics: Vec<Topic>,
+    security_protocol: SecurityProtocol,
+    sasl_mechanism: SaslMechanism,
+    sasl_credentials: Option<(String, String)>,
+}
+
+impl Kafka {
+    /// Provisions a topic once the broker is reachable.
+    ///
+    /// The topic is created with a `kafka-topics --create …` command that runs as part of
+    /// [`Image::exec_after_start`], after the image's [`ready_conditions`] have passed, so the
+    /// topic is available without a second round trip from the test.
+    ///
+    /// [`ready_conditions`]: Image::ready_conditions
+    pub fn with_topic(mut self, name: impl Into<String>, partitions: u32, replication: u32) -> Self {
+        self.topics.push(Topic {
+            name: name.into(),
+            partitions,
+            replication,
+        });
+        self
+    }
+
+    /// Switches the broker's external listener to the given security protocol.
+    ///
+    /// This rewrites `KAFKA_LISTENERS`, `KAFKA_ADVERTISED_LISTENERS` and the protocol map so the
+    /// external listener speaks `protocol` while the inter-broker `BROKER` listener stays on
+    /// `PLAINTEXT`. For a SASL protocol you'll usually pair this with
+    /// [`with_sasl_credentials`](Self::with_sasl_credentials).
+    pub fn with_security_protocol(mut self, protocol: SecurityProtocol) -> Self {
+        self.security_protocol = protocol;
+        self.apply_security();
+        self
+    }
+
+    /// Selects the SASL mechanism used on the external listener (defaults to
+    /// [`SaslMechanism::Plain`]).
+    pub fn with_sasl_mechanism(mut self, mechanism: SaslMechanism) -> Self {
+        self.sasl_mechanism = mechanism;
+        self.apply_security();
+        self
+    }
+
+    /// Sets the SASL credentials the external listener authenticates clients with.
+    ///
+    /// This injects the `KAFKA_SASL_ENABLED_MECHANISMS` var and an inline per-listener JAAS config
+    /// for the configured [`SaslMechanism`]. It only takes effect for a SASL [`SecurityProtocol`].
+    pub fn with_sasl_credentials(
+        mut self,
+        user: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.sasl_credentials = Some((user.into(), password.into()));
+        self.apply_security();
+        self
+    }
+
+    /// The advertised address clients should use to connect to the broker's external listener.
+    pub fn bootstrap_servers(&self) -> String {
+        format!("localhost:{}", KAFKA_PORT)
+    }
+
+    fn apply_security(&mut self) {
+        let protocol = self.security_protocol.as_str();
+
+        self.env_vars.insert(
+            "KAFKA_LISTENERS".to_owned(),
+            format!("{protocol}://0.0.0.0:{KAFKA_PORT},BROKER://0.0.0.0:9092"),
+        );
+        self.env_vars.insert(
+            "KAFKA_ADVERTISED_LISTENERS".to_owned(),
+            format!("{protocol}://localhost:{KAFKA_PORT},BROKER://localhost:9092"),
+        );
+        self.env_vars.insert(
+            "KAFKA_LISTENER_SECURITY_PROTOCOL_MAP".to_owned(),
+            format!("BROKER:PLAINTEXT,{protocol}:{protocol}"),
+        );
+
+        if self.security_protocol.is_sasl() {
+            if let Some((user, password)) = &self.sasl_credentials {
+                let mechanism = self.sasl_mechanism.as_str();
+
+                self.env_vars.insert(
+                    "KAFKA_SASL_ENABLED_MECHANISMS".to_owned(),
+                    mechanism.to_owned(),
+                );
+                // The inline per-listener JAAS config is self-contained; there is no external
+                // login-config file to point the JVM at via KAFKA_OPTS. The inter-broker `BROKER`
+                // listener stays PLAINTEXT, so no inter-broker SASL mechanism is configured.
+                self.env_vars.insert(
+                    format!(
+                        "KAFKA_LISTENER_NAME_{protocol}_{}_SASL_JAAS_CONFIG",
+                        self.sasl_mechanism.env_token()
+                    ),
+                    format!(
+                        "{} required username=\"{user}\" password=\"{password}\";",
+                        self.sasl_mechanism.login_module()
+                    ),
+                );
+            }
+        }
+    }
 }
 
 impl Default for Kafka {
@@ -78,6 +249,10 @@ impl Default for Kafka {
             arguments: KafkaArgs::default(),
             env_vars,
             tag: DEFAULT_TAG.to_owned(),
+            topics: Vec::new(),
+            security_protocol: SecurityProtocol::Plaintext,
+            sasl_mechanism: SaslMechanism::Plain,
+            sasl_credentials: None,
         }
     }
 }
@@ -100,4 +275,33 @@ impl Image for Kafka {
     fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
         Box::new(self.env_vars.iter())
     }
-}
\ No newline at end of file
+
+    fn exec_after_start(&self, _cs: &ContainerState) -> Vec<ExecCommand> {
+        // These commands run via `docker exec` inside the container, so they target the broker on
+        // its internal `BROKER` listener. That listener is always PLAINTEXT, so topic provisioning
+        // works regardless of the external listener's security protocol.
+        let bootstrap_server = format!("localhost:{}", BROKER_PORT);
+
+        self.topics
+            .iter()
+            .map(|topic| {
+                ExecCommand::new(vec![
+                    "kafka-topics".to_owned(),
+                    "--create".to_owned(),
+                    "--topic".to_owned(),
+                    topic.name.clone(),
+                    "--partitions".to_owned(),
+                    topic.partitions.to_string(),
+                    "--replication-factor".to_owned(),
+                    topic.replication.to_string(),
+                    "--bootstrap-server".to_owned(),
+                    bootstrap_server.clone(),
+                ])
+                .with_ready_condition(WaitFor::message_on_stdout(format!(
+                    "Created topic {}.",
+                    topic.name
+                )))
+            })
+            .collect()
+    }
+}
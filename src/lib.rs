@@ -0,0 +1,4 @@
+pub use crate::core::{ContainerState, ExecCommand, Image, WaitFor};
+
+pub mod core;
+pub mod images;
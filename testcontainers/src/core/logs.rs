@@ -1,8 +1,14 @@
 use conquer_once::Lazy;
+use regex::Regex;
 #[cfg(feature = "experimental")]
 use futures::{stream::BoxStream, StreamExt};
+use flate2::{write::GzEncoder, Compression};
 use std::{
-    fmt, io,
+    env,
+    error::Error,
+    fmt,
+    fs::{self, File},
+    io,
     io::{BufRead, BufReader, Read},
     num::NonZeroU8,
     path::{Path, PathBuf},
@@ -32,8 +38,9 @@ static LOGS_DUMP_DIR_PATH: Lazy<PathBuf> = Lazy::new(|| {
 });
 
 #[cfg(feature = "experimental")]
-pub(crate) struct LogStreamAsync<'d> {
+pub struct LogStreamAsync<'d> {
     inner: BoxStream<'d, Result<String, std::io::Error>>,
+    dump_context: Option<DumpContext>,
 }
 
 #[cfg(feature = "experimental")]
@@ -46,21 +53,80 @@ impl<'d> fmt::Debug for LogStreamAsync<'d> {
 #[cfg(feature = "experimental")]
 impl<'d> LogStreamAsync<'d> {
     pub fn new(stream: BoxStream<'d, Result<String, std::io::Error>>) -> Self {
-        Self { inner: stream }
+        Self {
+            inner: stream,
+            dump_context: None,
+        }
+    }
+
+    /// Labels the dump written on a failed `wait_for_*` call with the container name and stream
+    /// type. See [`LogStream::with_dump_context`].
+    pub(crate) fn with_dump_context(
+        mut self,
+        container_name: impl Into<String>,
+        stdtype: impl Into<String>,
+    ) -> Self {
+        self.dump_context = Some(DumpContext::new(container_name, stdtype));
+        self
+    }
+
+    pub async fn wait_for_message(self, message: &str) -> Result<(), WaitError> {
+        self.wait_for(Matcher::contains(message)).await
+    }
+
+    /// Waits until a log line matches the given regular expression.
+    pub async fn wait_for_message_matching(self, pattern: &str) -> Result<(), WaitError> {
+        self.wait_for(Matcher::matching(pattern)?).await
+    }
+
+    /// Waits until every one of `messages` has appeared on at least one line.
+    pub async fn wait_for_messages(self, messages: &[&str]) -> Result<(), WaitError> {
+        self.wait_for(Matcher::all_of(messages.iter().map(|m| Matcher::contains(*m))))
+            .await
     }
 
-    pub async fn wait_for_message(mut self, message: &str) -> Result<(), WaitError> {
+    async fn wait_for(self, mut matcher: Matcher) -> Result<(), WaitError> {
+        let dump_context = self.dump_context.clone();
         let mut lines = vec![];
+        let mut stream = self.follow();
 
-        while let Some(line) = self.inner.next().await.transpose()? {
-            if handle_line(line, message, &mut lines) {
-                return Ok(());
+        while let Some(line) = stream.next().await {
+            match line {
+                Ok(line) => {
+                    if handle_line(line, &mut matcher, &mut lines) {
+                        return Ok(());
+                    }
+                }
+                Err(cause) => {
+                    dump_on_failure(dump_context.as_ref(), &lines);
+                    return Err(end_of_stream_with_cause(lines, Box::new(cause)));
+                }
             }
         }
 
+        dump_on_failure(dump_context.as_ref(), &lines);
         Err(end_of_stream(lines))
     }
 
+    /// Returns the underlying stream of log lines, so the container's output can keep being read
+    /// line-by-line after startup.
+    ///
+    /// Each item is a single line with the trailing newline stripped, or an [`io::Error`] if
+    /// reading the stream failed.
+    ///
+    /// There is no stdout/stderr selector here: a `LogStreamAsync` wraps a *single* already-chosen
+    /// stream. Which of the container's streams it carries is decided where the handle builds it
+    /// (one `LogStreamAsync` per std type), so selection belongs to the container handle, not to
+    /// this consuming end.
+    pub fn follow(self) -> BoxStream<'d, Result<String, std::io::Error>> {
+        self.inner
+    }
+
+    /// Alias for [`follow`](Self::follow).
+    pub fn logs(self) -> BoxStream<'d, Result<String, std::io::Error>> {
+        self.follow()
+    }
+
     pub(crate) fn into_inner(self) -> BoxStream<'d, Result<String, std::io::Error>> {
         self.inner
     }
@@ -68,6 +134,7 @@ impl<'d> LogStreamAsync<'d> {
 
 pub(crate) struct LogStream {
     inner: Box<dyn Read>,
+    dump_context: Option<DumpContext>,
 }
 
 impl fmt::Debug for LogStream {
@@ -80,19 +147,58 @@ impl LogStream {
     pub fn new(stream: impl Read + 'static) -> Self {
         Self {
             inner: Box::new(stream),
+            dump_context: None,
         }
     }
 
+    /// Labels the dump written on a failed `wait_for_*` call with the container name and stream
+    /// type (`"stdout"`/`"stderr"`).
+    ///
+    /// Without a dump context nothing is written; with one, a dump is written only when
+    /// per-container log dumping is enabled (see [`LogDumpMode::from_env`]).
+    pub(crate) fn with_dump_context(
+        mut self,
+        container_name: impl Into<String>,
+        stdtype: impl Into<String>,
+    ) -> Self {
+        self.dump_context = Some(DumpContext::new(container_name, stdtype));
+        self
+    }
+
     pub fn wait_for_message(self, message: &str) -> Result<(), WaitError> {
-        let logs = BufReader::new(self.inner);
+        self.wait_for(Matcher::contains(message))
+    }
+
+    /// Waits until a log line matches the given regular expression.
+    pub fn wait_for_message_matching(self, pattern: &str) -> Result<(), WaitError> {
+        self.wait_for(Matcher::matching(pattern)?)
+    }
+
+    /// Waits until every one of `messages` has appeared on at least one line.
+    pub fn wait_for_messages(self, messages: &[&str]) -> Result<(), WaitError> {
+        self.wait_for(Matcher::all_of(messages.iter().map(|m| Matcher::contains(*m))))
+    }
+
+    fn wait_for(self, mut matcher: Matcher) -> Result<(), WaitError> {
+        let LogStream { inner, dump_context } = self;
+        let logs = BufReader::new(inner);
         let mut lines = vec![];
 
         for line in logs.lines() {
-            if handle_line(line?, message, &mut lines) {
-                return Ok(());
+            match line {
+                Ok(line) => {
+                    if handle_line(line, &mut matcher, &mut lines) {
+                        return Ok(());
+                    }
+                }
+                Err(cause) => {
+                    dump_on_failure(dump_context.as_ref(), &lines);
+                    return Err(end_of_stream_with_cause(lines, Box::new(cause)));
+                }
             }
         }
 
+        dump_on_failure(dump_context.as_ref(), &lines);
         Err(end_of_stream(lines))
     }
 
@@ -101,9 +207,12 @@ impl LogStream {
     }
 }
 
-fn handle_line(line: String, message: &str, lines: &mut Vec<String>) -> bool {
-    if line.contains(message) {
-        log::info!("Found message after comparing {} lines", lines.len());
+fn handle_line(line: String, matcher: &mut Matcher, lines: &mut Vec<String>) -> bool {
+    if matcher.register(&line) {
+        log::info!(
+            "Found all required messages after comparing {} lines",
+            lines.len()
+        );
 
         return true;
     }
@@ -113,22 +222,115 @@ fn handle_line(line: String, message: &str, lines: &mut Vec<String>) -> bool {
     false
 }
 
+/// A single readiness pattern that a log line can satisfy.
+#[derive(Debug)]
+enum LogPattern {
+    Contains(String),
+    Matches(Regex),
+}
+
+impl LogPattern {
+    fn matches(&self, line: &str) -> bool {
+        match self {
+            LogPattern::Contains(message) => line.contains(message),
+            LogPattern::Matches(re) => re.is_match(line),
+        }
+    }
+}
+
+/// Tracks which of several required patterns have been seen while scanning a log stream.
+///
+/// A line is fed to [`Matcher::register`], which marks every still-unsatisfied pattern it matches
+/// and returns `true` only once every required pattern has matched at least one line.
+#[derive(Debug)]
+pub(crate) struct Matcher {
+    required: Vec<(LogPattern, bool)>,
+}
+
+impl Matcher {
+    /// Matches lines that contain `message` as a substring.
+    pub(crate) fn contains(message: impl Into<String>) -> Self {
+        Self {
+            required: vec![(LogPattern::Contains(message.into()), false)],
+        }
+    }
+
+    /// Matches lines against a regular expression.
+    ///
+    /// Returns [`WaitError::InvalidRegex`] if `pattern` fails to compile, so a bad pattern isn't
+    /// silently treated as a condition that never matches.
+    pub(crate) fn matching(pattern: &str) -> Result<Self, WaitError> {
+        let re = Regex::new(pattern).map_err(WaitError::InvalidRegex)?;
+
+        Ok(Self {
+            required: vec![(LogPattern::Matches(re), false)],
+        })
+    }
+
+    /// Requires that every pattern in `matchers` is satisfied at least once.
+    pub(crate) fn all_of(matchers: impl IntoIterator<Item = Matcher>) -> Self {
+        Self {
+            required: matchers
+                .into_iter()
+                .flat_map(|matcher| matcher.required)
+                .collect(),
+        }
+    }
+
+    /// Feeds a line to the matcher, returning `true` once every required pattern has matched.
+    fn register(&mut self, line: &str) -> bool {
+        for (pattern, satisfied) in self.required.iter_mut() {
+            if !*satisfied && pattern.matches(line) {
+                *satisfied = true;
+            }
+        }
+
+        self.required.iter().all(|(_, satisfied)| *satisfied)
+    }
+}
+
 fn end_of_stream(lines: Vec<String>) -> WaitError {
     log::error!(
         "Failed to find message in stream after comparing {} lines.",
         lines.len()
     );
 
-    WaitError::EndOfStream(lines)
+    WaitError::EndOfStream { lines, cause: None }
+}
+
+/// Builds an [`WaitError::EndOfStream`] that also carries the error which terminated the stream
+/// (a timeout or transport failure), so the root cause isn't lost.
+fn end_of_stream_with_cause(
+    lines: Vec<String>,
+    cause: Box<dyn Error + Send + Sync>,
+) -> WaitError {
+    log::error!(
+        "Log stream terminated after comparing {} lines: {}",
+        lines.len(),
+        cause
+    );
+
+    WaitError::EndOfStream {
+        lines,
+        cause: Some(cause),
+    }
 }
 
 /// Defines error cases when waiting for a message in a stream.
 #[derive(Debug)]
 pub enum WaitError {
     /// Indicates the stream ended before finding the log line you were looking for.
-    /// Contains all the lines that were read for debugging purposes.
-    EndOfStream(Vec<String>),
+    ///
+    /// Carries all the lines that were read for debugging purposes, and the underlying `cause`
+    /// (a timeout or transport error) when the stream was terminated by something other than a
+    /// clean end-of-file.
+    EndOfStream {
+        lines: Vec<String>,
+        cause: Option<Box<dyn Error + Send + Sync>>,
+    },
     Io(io::Error),
+    /// A readiness pattern failed to compile as a regular expression.
+    InvalidRegex(regex::Error),
 }
 
 impl From<io::Error> for WaitError {
@@ -137,6 +339,41 @@ impl From<io::Error> for WaitError {
     }
 }
 
+impl fmt::Display for WaitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitError::EndOfStream { lines, .. } => {
+                write!(
+                    f,
+                    "log stream ended before the expected message appeared after {} lines",
+                    lines.len()
+                )?;
+
+                let tail = lines.len().saturating_sub(3);
+                if let Some(last) = lines.get(tail..).filter(|tail| !tail.is_empty()) {
+                    write!(f, "; last lines: {}", last.join(" | "))?;
+                }
+
+                Ok(())
+            }
+            WaitError::Io(e) => write!(f, "failed to read the container log stream: {e}"),
+            WaitError::InvalidRegex(e) => write!(f, "invalid readiness pattern: {e}"),
+        }
+    }
+}
+
+impl Error for WaitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WaitError::EndOfStream { cause, .. } => {
+                cause.as_ref().map(|cause| cause.as_ref() as &(dyn Error + 'static))
+            }
+            WaitError::Io(e) => Some(e),
+            WaitError::InvalidRegex(e) => Some(e),
+        }
+    }
+}
+
 pub(crate) fn get_log_dump_file_path(
     log_dump_dir: &Path,
     container_name: &str,
@@ -154,6 +391,104 @@ pub(crate) fn get_log_dump_dir_path() -> PathBuf {
     LOGS_DUMP_DIR_PATH.clone()
 }
 
+/// Environment variable that opts a run into dumping container logs on failure.
+///
+/// Set it to `gzip` to compress the dumps; any other non-empty value leaves them uncompressed.
+pub(crate) const LOG_DUMP_ENV_VAR: &str = "TESTCONTAINERS_LOG_DUMP";
+
+/// The log-dumping mode selected for this run, derived from [`LOG_DUMP_ENV_VAR`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LogDumpMode {
+    Off,
+    Plain,
+    Gzip,
+}
+
+impl LogDumpMode {
+    /// Resolves the mode from the `TESTCONTAINERS_LOG_DUMP` environment variable.
+    pub(crate) fn from_env() -> Self {
+        match env::var(LOG_DUMP_ENV_VAR) {
+            Ok(value) if value.eq_ignore_ascii_case("gzip") => LogDumpMode::Gzip,
+            Ok(value) if !value.is_empty() => LogDumpMode::Plain,
+            _ => LogDumpMode::Off,
+        }
+    }
+}
+
+/// Identifies a container stream so a log dump can be named after it.
+#[derive(Debug, Clone)]
+pub(crate) struct DumpContext {
+    container_name: String,
+    stdtype: String,
+}
+
+impl DumpContext {
+    fn new(container_name: impl Into<String>, stdtype: impl Into<String>) -> Self {
+        Self {
+            container_name: container_name.into(),
+            stdtype: stdtype.into(),
+        }
+    }
+}
+
+/// Dumps the lines captured before a failed `wait_for_*` call to disk, if the stream carries a
+/// [`DumpContext`] and dumping is enabled via [`LogDumpMode::from_env`].
+///
+/// Only the lines read by the failed wait are written, not the container's full stdout/stderr.
+/// Logs are not dumped when a container is dropped or a test panics: a dump is written only here,
+/// for a stream that was given a [`DumpContext`] via [`LogStream::with_dump_context`].
+///
+/// Any error is logged rather than propagated, so a dump failure never masks the original wait
+/// failure being returned to the caller.
+fn dump_on_failure(context: Option<&DumpContext>, lines: &[String]) {
+    let context = match context {
+        Some(context) => context,
+        None => return,
+    };
+
+    let mode = LogDumpMode::from_env();
+    let buffer = lines.join("\n").into_bytes();
+
+    if let Err(e) = dump_logs(buffer.as_slice(), &context.container_name, &context.stdtype, mode) {
+        log::warn!("Failed to dump container {} logs: {}", context.stdtype, e);
+    }
+}
+
+/// Drains a container stream into a per-container dump file under [`get_log_dump_dir_path`].
+///
+/// The file is named `<container>_<stdtype>.log` (or `.log.gz` when `mode` is [`LogDumpMode::Gzip`])
+/// via [`get_log_dump_file_path`], and the resulting path is logged at `info` level so CI users can
+/// find the artifact. Returns `Ok(None)` when dumping is disabled.
+pub(crate) fn dump_logs(
+    stream: impl Read,
+    container_name: &str,
+    stdtype: &str,
+    mode: LogDumpMode,
+) -> io::Result<Option<PathBuf>> {
+    if mode == LogDumpMode::Off {
+        return Ok(None);
+    }
+
+    let dir = get_log_dump_dir_path();
+    fs::create_dir_all(&dir)?;
+
+    let mut path = get_log_dump_file_path(&dir, container_name, stdtype);
+    let mut reader = BufReader::new(stream);
+
+    if mode == LogDumpMode::Gzip {
+        path.set_extension("log.gz");
+        let mut encoder = GzEncoder::new(File::create(&path)?, Compression::default());
+        io::copy(&mut reader, &mut encoder)?;
+        encoder.finish()?;
+    } else {
+        io::copy(&mut reader, &mut File::create(&path)?)?;
+    }
+
+    log::info!("Dumped container {} logs to {}", stdtype, path.display());
+
+    Ok(Some(path))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,4 +508,73 @@ mod tests {
 
         assert!(result.is_ok())
     }
+
+    #[test]
+    fn given_logs_when_line_matches_pattern_should_find_it() {
+        let log_stream = LogStream::new(
+            r"
+            Message one
+            Message two
+            Message three
+        "
+            .as_bytes(),
+        );
+
+        let result = log_stream.wait_for_message_matching("Message t.*");
+
+        assert!(result.is_ok())
+    }
+
+    #[test]
+    fn given_logs_when_all_messages_appear_should_find_them() {
+        let log_stream = LogStream::new(
+            r"
+            Message one
+            Message two
+            Message three
+        "
+            .as_bytes(),
+        );
+
+        let result = log_stream.wait_for_messages(&["Message one", "Message three"]);
+
+        assert!(result.is_ok())
+    }
+
+    #[test]
+    fn given_an_invalid_pattern_should_surface_a_distinct_error() {
+        let log_stream = LogStream::new("".as_bytes());
+
+        let result = log_stream.wait_for_message_matching("Message (");
+
+        assert!(matches!(result, Err(WaitError::InvalidRegex(_))))
+    }
+
+    #[test]
+    fn given_a_dump_context_when_the_wait_fails_the_stream_drains_without_panicking() {
+        let log_stream = LogStream::new("Message one".as_bytes())
+            .with_dump_context("confluentinc/cp-kafka", "stdout");
+
+        let result = log_stream.wait_for_message("never appears");
+
+        assert!(matches!(result, Err(WaitError::EndOfStream { .. })))
+    }
+
+    #[test]
+    fn dump_logs_writes_the_captured_lines_to_the_dump_path() {
+        let container_name = "confluentinc/cp-kafka";
+        let path = dump_logs(
+            "line one\nline two".as_bytes(),
+            container_name,
+            "stdout",
+            LogDumpMode::Plain,
+        )
+        .expect("dump should succeed")
+        .expect("dump should produce a path when enabled");
+
+        let contents = fs::read_to_string(&path).expect("dump file should exist");
+        assert_eq!(contents, "line one\nline two");
+
+        fs::remove_file(&path).ok();
+    }
 }